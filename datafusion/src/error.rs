@@ -0,0 +1,50 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! DataFusion error type
+
+use std::fmt;
+
+/// Result type for operations that could result in a [`DataFusionError`]
+pub type Result<T> = std::result::Result<T, DataFusionError>;
+
+/// Error type used throughout DataFusion
+#[derive(Debug)]
+pub enum DataFusionError {
+    /// Error returned by an arrow operation (building an array, a batch, ...)
+    ArrowError(arrow::error::ArrowError),
+    /// An internal invariant was violated, e.g. a plan was asked to execute
+    /// a partition that doesn't exist
+    Internal(String),
+}
+
+impl fmt::Display for DataFusionError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DataFusionError::ArrowError(desc) => write!(f, "Arrow error: {}", desc),
+            DataFusionError::Internal(desc) => write!(f, "Internal error: {}", desc),
+        }
+    }
+}
+
+impl std::error::Error for DataFusionError {}
+
+impl From<arrow::error::ArrowError> for DataFusionError {
+    fn from(e: arrow::error::ArrowError) -> Self {
+        DataFusionError::ArrowError(e)
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Logical plan types shared by the planner and `EXPLAIN`
+
+use std::sync::Arc;
+
+/// A scalar or column expression referenced by a logical or physical plan
+#[derive(Debug, Clone, PartialEq)]
+pub enum Expr {
+    /// Reference to a column by name
+    Column(String),
+}
+
+/// Which stage of planning a [`StringifiedPlan`] captures. Of the planning
+/// stages, only `FinalLogicalPlan` and `FinalPhysicalPlan` are shown unless
+/// `EXPLAIN VERBOSE` was used; the other stages let a user see what each
+/// optimizer rule changed. The `PhysicalPlanWithPartitioning`,
+/// `PhysicalPlanJson` and `PhysicalPlanGraphviz` renderings are always shown.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlanType {
+    /// The initial logical plan, before any optimizer rule has run
+    InitialLogicalPlan,
+    /// The logical plan after all logical optimizer rules have run
+    FinalLogicalPlan,
+    /// The initial physical plan, before any physical optimizer rule has run
+    InitialPhysicalPlan,
+    /// The physical plan after the named physical optimizer rule has run
+    OptimizedPhysicalPlan {
+        /// The name of the rule that produced this snapshot
+        optimizer_name: String,
+    },
+    /// The physical plan after all physical optimizer rules have run
+    FinalPhysicalPlan,
+    /// The physical plan annotated with each node's `output_partitioning()`
+    /// and `required_child_distribution()`
+    PhysicalPlanWithPartitioning,
+    /// The physical plan, serialized as a single JSON string
+    PhysicalPlanJson,
+    /// The physical plan, rendered as a Graphviz `digraph`
+    PhysicalPlanGraphviz,
+}
+
+impl PlanType {
+    /// Whether a plan of this type should be included in `EXPLAIN` output.
+    /// Of the planning stages, only the final logical and final physical
+    /// plans are shown unless `verbose` is set, in which case every stage is
+    /// shown. The partitioning, JSON and Graphviz renderings are always shown.
+    pub fn should_display(&self, verbose: bool) -> bool {
+        match self {
+            PlanType::FinalLogicalPlan | PlanType::FinalPhysicalPlan => true,
+            PlanType::InitialLogicalPlan
+            | PlanType::InitialPhysicalPlan
+            | PlanType::OptimizedPhysicalPlan { .. } => verbose,
+            // Always shown: these are only produced when a user explicitly
+            // asks for them (an annotated/JSON EXPLAIN), not as part of the
+            // normal optimizer-stage progression.
+            PlanType::PhysicalPlanWithPartitioning
+            | PlanType::PhysicalPlanJson
+            | PlanType::PhysicalPlanGraphviz => true,
+        }
+    }
+}
+
+impl From<&PlanType> for String {
+    fn from(t: &PlanType) -> Self {
+        match t {
+            PlanType::InitialLogicalPlan => "initial_logical_plan".to_string(),
+            PlanType::FinalLogicalPlan => "logical_plan".to_string(),
+            PlanType::InitialPhysicalPlan => "initial_physical_plan".to_string(),
+            PlanType::OptimizedPhysicalPlan { optimizer_name } => {
+                format!("physical_plan after {}", optimizer_name)
+            }
+            PlanType::FinalPhysicalPlan => "physical_plan".to_string(),
+            PlanType::PhysicalPlanWithPartitioning => {
+                "physical_plan_with_partitioning".to_string()
+            }
+            PlanType::PhysicalPlanJson => "physical_plan_json".to_string(),
+            PlanType::PhysicalPlanGraphviz => "physical_plan_graphviz".to_string(),
+        }
+    }
+}
+
+/// A snapshot of a plan, stringified at a particular [`PlanType`] stage, as
+/// displayed by `EXPLAIN` / `ExplainExec`
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringifiedPlan {
+    /// Which stage of planning this snapshot was taken at
+    pub plan_type: PlanType,
+    /// The `Display`/`Debug` representation of the plan at that stage
+    pub plan: Arc<String>,
+}
+
+impl StringifiedPlan {
+    /// Create a new stringified snapshot of `plan` at the given `plan_type`
+    pub fn new(plan_type: PlanType, plan: impl Into<String>) -> Self {
+        StringifiedPlan {
+            plan_type,
+            plan: Arc::new(plan.into()),
+        }
+    }
+
+    /// Whether this plan should be included in `EXPLAIN` output; delegates
+    /// to [`PlanType::should_display`]
+    pub fn should_display(&self, verbose: bool) -> bool {
+        self.plan_type.should_display(verbose)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn final_plans_always_display() {
+        assert!(PlanType::FinalLogicalPlan.should_display(false));
+        assert!(PlanType::FinalPhysicalPlan.should_display(false));
+        assert!(PlanType::FinalLogicalPlan.should_display(true));
+        assert!(PlanType::FinalPhysicalPlan.should_display(true));
+    }
+
+    #[test]
+    fn optimizer_stage_plans_only_display_when_verbose() {
+        let optimized = PlanType::OptimizedPhysicalPlan {
+            optimizer_name: "rule".to_string(),
+        };
+        for plan_type in [
+            PlanType::InitialLogicalPlan,
+            PlanType::InitialPhysicalPlan,
+            optimized,
+        ] {
+            assert!(!plan_type.should_display(false));
+            assert!(plan_type.should_display(true));
+        }
+    }
+
+    #[test]
+    fn explicit_format_plans_always_display() {
+        for plan_type in [
+            PlanType::PhysicalPlanWithPartitioning,
+            PlanType::PhysicalPlanJson,
+            PlanType::PhysicalPlanGraphviz,
+        ] {
+            assert!(plan_type.should_display(false));
+            assert!(plan_type.should_display(true));
+        }
+    }
+}
@@ -0,0 +1,320 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the EXPLAIN ANALYZE operator
+
+use std::any::Any;
+use std::sync::Arc;
+
+use crate::{
+    error::{DataFusionError, Result},
+    physical_plan::{
+        common::SizedRecordBatchStream,
+        display::{self, walk_plan},
+        metrics::MetricsSet,
+        DisplayFormatType, ExecutionPlan, Partitioning,
+    },
+};
+use crate::physical_plan::LambdaExecPlan;
+use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
+
+use super::SendableRecordBatchStream;
+use async_trait::async_trait;
+use futures::StreamExt;
+
+use serde::{Deserialize, Serialize};
+
+/// `EXPLAIN ANALYZE` execution plan operator. Drives its single child plan
+/// to completion, discarding the rows it produces, then reports the runtime
+/// metrics (`ExecutionPlan::metrics()`) every node in the child's plan tree
+/// recorded while executing.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AnalyzeExec {
+    /// The schema that this exec plan node outputs
+    schema: SchemaRef,
+    /// The plan being analyzed
+    input: Arc<dyn ExecutionPlan>,
+    /// Metrics this `AnalyzeExec` itself records while driving `input` to
+    /// completion. Shared via `Arc` so `execute()` (which only has `&self`)
+    /// can update it as it streams.
+    #[serde(skip, default)]
+    metrics: Arc<MetricsSet>,
+}
+
+impl AnalyzeExec {
+    /// Create a new AnalyzeExec
+    pub fn new(schema: SchemaRef, input: Arc<dyn ExecutionPlan>) -> Self {
+        AnalyzeExec {
+            schema,
+            input,
+            metrics: Arc::new(MetricsSet::new()),
+        }
+    }
+
+    /// The plan being analyzed
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+}
+
+#[async_trait]
+#[typetag::serde(name = "analyze_exec")]
+impl ExecutionPlan for AnalyzeExec {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn as_mut_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    /// Get the output partitioning of this plan
+    fn output_partitioning(&self) -> Partitioning {
+        Partitioning::UnknownPartitioning(1)
+    }
+
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.len() == 1 {
+            Ok(Arc::new(AnalyzeExec::new(
+                self.schema.clone(),
+                children.into_iter().next().unwrap(),
+            )))
+        } else {
+            Err(DataFusionError::Internal(format!(
+                "AnalyzeExec wrong number of children {}",
+                children.len()
+            )))
+        }
+    }
+
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream> {
+        if 0 != partition {
+            return Err(DataFusionError::Internal(format!(
+                "AnalyzeExec invalid partition {}",
+                partition
+            )));
+        }
+
+        // Drive every partition of the child plan to completion, discarding
+        // the rows: we only care about the metrics recorded while doing so.
+        // `AnalyzeExec` itself records output_rows/elapsed_compute/
+        // partition_count for this wrapped execution; children of `input`
+        // only contribute metrics if they implement `ExecutionPlan::metrics`
+        // themselves, which defaults to `None`.
+        let input_partitions = self.input.output_partitioning().partition_count();
+        for p in 0..input_partitions {
+            self.metrics.partition_count.add(1);
+            let timer = self.metrics.elapsed_compute.timer();
+            let mut stream = self.input.execute(p).await?;
+            while let Some(batch) = stream.next().await.transpose()? {
+                self.metrics.output_rows.add(batch.num_rows());
+            }
+            drop(timer);
+        }
+
+        let mut type_builder = StringBuilder::new(1);
+        let mut plan_builder = StringBuilder::new(1);
+
+        let mut lines = Vec::new();
+        walk_plan(self.input.as_ref(), 0, &mut lines, &|node| {
+            format!(
+                "{}, metrics=[{}]",
+                display::IndentedNode(node),
+                node.metrics()
+                    .map(|m| m.to_string())
+                    .unwrap_or_else(|| "none".to_string()),
+            )
+        });
+        type_builder.append_value("Plan with Metrics")?;
+        plan_builder.append_value(lines.join("\n"))?;
+
+        let record_batch = RecordBatch::try_new(
+            self.schema.clone(),
+            vec![
+                Arc::new(type_builder.finish()),
+                Arc::new(plan_builder.finish()),
+            ],
+        )?;
+
+        Ok(Box::pin(SizedRecordBatchStream::new(
+            self.schema.clone(),
+            vec![Arc::new(record_batch)],
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.snapshot())
+    }
+
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default => {
+                write!(f, "AnalyzeExec")
+            }
+            DisplayFormatType::Json => display::write_json_node(f, self),
+            DisplayFormatType::Graphviz => display::write_graphviz_node(f, self),
+        }
+    }
+}
+
+#[async_trait]
+impl LambdaExecPlan for AnalyzeExec {
+    fn feed_batches(&mut self, _partitions: Vec<Vec<RecordBatch>>) {
+        unimplemented!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use futures::executor::block_on;
+
+    /// A leaf plan that yields a single batch of `rows` rows and reports a
+    /// fixed `metrics()` snapshot, just enough to exercise
+    /// `AnalyzeExec::execute` without a real operator.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockExec {
+        schema: SchemaRef,
+        rows: usize,
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_exec_for_analyze_test")]
+    impl ExecutionPlan for MockExec {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_mut_any(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+            vec![]
+        }
+
+        fn output_partitioning(&self) -> Partitioning {
+            Partitioning::UnknownPartitioning(1)
+        }
+
+        fn with_new_children(
+            &self,
+            _children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(self.clone()))
+        }
+
+        async fn execute(&self, _partition: usize) -> Result<SendableRecordBatchStream> {
+            let array = Int32Array::from(vec![0; self.rows]);
+            let batch = RecordBatch::try_new(self.schema.clone(), vec![Arc::new(array)])?;
+            Ok(Box::pin(SizedRecordBatchStream::new(
+                self.schema.clone(),
+                vec![Arc::new(batch)],
+            )))
+        }
+
+        fn metrics(&self) -> Option<MetricsSet> {
+            let metrics = MetricsSet::new();
+            metrics.output_rows.add(self.rows);
+            Some(metrics)
+        }
+
+        fn fmt_as(
+            &self,
+            _t: DisplayFormatType,
+            f: &mut std::fmt::Formatter,
+        ) -> std::fmt::Result {
+            write!(f, "MockExec")
+        }
+    }
+
+    fn explain_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("plan_type", DataType::Utf8, false),
+            Field::new("plan", DataType::Utf8, false),
+        ]))
+    }
+
+    #[test]
+    fn records_output_rows_and_exposes_metrics() {
+        let child_schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let child: Arc<dyn ExecutionPlan> = Arc::new(MockExec {
+            schema: child_schema,
+            rows: 7,
+        });
+        let analyze = AnalyzeExec::new(explain_schema(), child);
+
+        let mut stream = block_on(analyze.execute(0)).unwrap();
+        let batch = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 1);
+
+        let metrics = analyze
+            .metrics()
+            .expect("AnalyzeExec always reports metrics");
+        assert_eq!(metrics.output_rows.value(), 7);
+        assert_eq!(metrics.partition_count.value(), 1);
+    }
+
+    #[test]
+    fn rendered_plan_includes_the_childs_own_metrics() {
+        // The "plan" column is supposed to show *per-operator* runtime
+        // statistics, not just AnalyzeExec's own aggregate counters: a
+        // child that reports `metrics()` must have those values show up in
+        // the rendered output rather than the "none" placeholder.
+        let child_schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        let child: Arc<dyn ExecutionPlan> = Arc::new(MockExec {
+            schema: child_schema,
+            rows: 5,
+        });
+        let analyze = AnalyzeExec::new(explain_schema(), child);
+
+        let mut stream = block_on(analyze.execute(0)).unwrap();
+        let batch = block_on(stream.next()).unwrap().unwrap();
+        let plan_col = batch
+            .column(1)
+            .as_any()
+            .downcast_ref::<arrow::array::StringArray>()
+            .unwrap();
+        let rendered = plan_col.value(0);
+
+        assert!(rendered.contains("MockExec"));
+        assert!(rendered.contains("output_rows=5"));
+        assert!(!rendered.contains("metrics=[none]"));
+    }
+}
@@ -0,0 +1,69 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Common utilities shared by physical operators
+
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use futures::Stream;
+
+use crate::error::Result;
+
+/// A `SendableRecordBatchStream` over a fixed, already materialized list of
+/// `RecordBatch`es, used by operators (like `ExplainExec`) whose output is
+/// computed eagerly rather than streamed lazily.
+pub struct SizedRecordBatchStream {
+    schema: SchemaRef,
+    batches: Vec<Arc<RecordBatch>>,
+    index: usize,
+}
+
+impl SizedRecordBatchStream {
+    /// Create a stream that yields `batches` in order and then ends
+    pub fn new(schema: SchemaRef, batches: Vec<Arc<RecordBatch>>) -> Self {
+        Self {
+            schema,
+            batches,
+            index: 0,
+        }
+    }
+}
+
+impl Stream for SizedRecordBatchStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        Poll::Ready(if self.index < self.batches.len() {
+            let batch = self.batches[self.index].as_ref().clone();
+            self.index += 1;
+            Some(Ok(batch))
+        } else {
+            None
+        })
+    }
+}
+
+impl SizedRecordBatchStream {
+    /// The schema of the batches this stream yields
+    pub fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
@@ -0,0 +1,286 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Shared helpers for rendering an [`ExecutionPlan`] tree, used by
+//! `ExplainExec` and `AnalyzeExec` to turn a plan into text.
+
+use std::fmt;
+
+use super::ExecutionPlan;
+use crate::physical_plan::DisplayFormatType;
+
+/// Walk `plan` depth-first, appending one line per node to `out`. Each line
+/// is produced by `render` and indented two spaces per level of nesting.
+pub(crate) fn walk_plan<F>(plan: &dyn ExecutionPlan, depth: usize, out: &mut Vec<String>, render: &F)
+where
+    F: Fn(&dyn ExecutionPlan) -> String,
+{
+    out.push(format!("{}{}", "  ".repeat(depth), render(plan)));
+    for child in plan.children() {
+        walk_plan(child.as_ref(), depth + 1, out, render);
+    }
+}
+
+/// Adapts a node's `fmt_as(Default, ...)` label, annotated with its
+/// `output_partitioning()` and `required_child_distribution()`, to
+/// `Display`. Used to make it visible where a merge/repartition boundary
+/// would be inserted when reasoning about parallelism.
+pub(crate) struct IndentedNode<'a>(pub &'a dyn ExecutionPlan);
+
+impl<'a> fmt::Display for IndentedNode<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_as(DisplayFormatType::Default, f)?;
+        write!(
+            f,
+            ", partitioning={:?}, required_child_dist={}",
+            self.0.output_partitioning(),
+            self.0.required_child_distribution()
+        )
+    }
+}
+
+/// Adapts a node's `fmt_as(Json, ...)` representation to `Display`, so a
+/// whole plan tree can be rendered as a single JSON string via
+/// `write!(f, "{}", Json(plan))`.
+pub(crate) struct Json<'a>(pub &'a dyn ExecutionPlan);
+
+impl<'a> fmt::Display for Json<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_as(DisplayFormatType::Json, f)
+    }
+}
+
+/// `plan`'s `fmt_as(Default, ...)` label, used as the `"name"` of its JSON
+/// node representation.
+fn default_label(plan: &dyn ExecutionPlan) -> String {
+    struct DefaultLabel<'a>(&'a dyn ExecutionPlan);
+    impl<'a> fmt::Display for DefaultLabel<'a> {
+        fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            self.0.fmt_as(DisplayFormatType::Default, f)
+        }
+    }
+    DefaultLabel(plan).to_string()
+}
+
+/// Build `plan`'s JSON node representation —
+/// `{"name":..,"schema":..,"output_partitioning":..,"children":[..]}` —
+/// recursing into each child, as a `serde_json::Value` so string fields
+/// (schema/partitioning debug dumps, which may contain quotes or braces of
+/// their own) are properly escaped and the result is always valid JSON.
+fn json_value(plan: &dyn ExecutionPlan) -> serde_json::Value {
+    serde_json::json!({
+        "name": default_label(plan),
+        "schema": format!("{:?}", plan.schema()),
+        "output_partitioning": format!("{:?}", plan.output_partitioning()),
+        "children": plan.children().iter().map(|c| json_value(c.as_ref())).collect::<Vec<_>>(),
+    })
+}
+
+/// Write `plan`'s JSON node representation (see [`json_value`]) to `f`.
+/// Operators implement `fmt_as(DisplayFormatType::Json, ...)` by delegating
+/// to this so the resulting tree can be reconstructed by a remote
+/// coordinator to identify each node's type and topology.
+pub(crate) fn write_json_node(f: &mut fmt::Formatter, plan: &dyn ExecutionPlan) -> fmt::Result {
+    write!(f, "{}", json_value(plan))
+}
+
+/// Adapts a node's `fmt_as(Graphviz, ...)` representation to `Display`, so a
+/// whole plan tree can be rendered as a single Graphviz `digraph` via
+/// `write!(f, "{}", Graphviz(plan))`.
+pub(crate) struct Graphviz<'a>(pub &'a dyn ExecutionPlan);
+
+impl<'a> fmt::Display for Graphviz<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.0.fmt_as(DisplayFormatType::Graphviz, f)
+    }
+}
+
+/// Escape a label so it can be embedded in a Graphviz `"..."` string:
+/// backslashes and quotes are the only characters DOT itself treats
+/// specially inside a quoted string.
+fn escape_dot(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Write one node (as `nN [label="..."];`) and its outgoing edges to its
+/// children, recursing depth-first. `counter` assigns each node a unique,
+/// stable `nN` id as it is visited.
+fn write_graphviz_nodes(
+    plan: &dyn ExecutionPlan,
+    counter: &mut usize,
+    f: &mut fmt::Formatter,
+) -> fmt::Result {
+    let id = *counter;
+    *counter += 1;
+    writeln!(f, "  n{} [label=\"{}\"];", id, escape_dot(&default_label(plan)))?;
+    for child in plan.children() {
+        let child_id = *counter;
+        write_graphviz_nodes(child.as_ref(), counter, f)?;
+        writeln!(f, "  n{} -> n{};", id, child_id)?;
+    }
+    Ok(())
+}
+
+/// Write `plan`'s full Graphviz `digraph` representation to `f`. Operators
+/// implement `fmt_as(DisplayFormatType::Graphviz, ...)` by delegating to
+/// this so the result can be piped straight into `dot` to visualize the
+/// plan tree.
+pub(crate) fn write_graphviz_node(f: &mut fmt::Formatter, plan: &dyn ExecutionPlan) -> fmt::Result {
+    writeln!(f, "digraph {{")?;
+    write_graphviz_nodes(plan, &mut 0, f)?;
+    write!(f, "}}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::Result;
+    use crate::physical_plan::{Partitioning, SendableRecordBatchStream};
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use async_trait::async_trait;
+    use serde::{Deserialize, Serialize};
+    use std::any::Any;
+    use std::sync::Arc;
+
+    /// A leaf or parent plan with a fixed label and schema, just enough to
+    /// exercise the display helpers without a real operator.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockExec {
+        label: &'static str,
+        schema: SchemaRef,
+        #[serde(skip)]
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_exec_for_display_test")]
+    impl ExecutionPlan for MockExec {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_mut_any(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+            self.children.clone()
+        }
+
+        fn output_partitioning(&self) -> Partitioning {
+            Partitioning::UnknownPartitioning(1)
+        }
+
+        fn with_new_children(
+            &self,
+            _children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(self.clone()))
+        }
+
+        async fn execute(&self, _partition: usize) -> Result<SendableRecordBatchStream> {
+            unimplemented!()
+        }
+
+        fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+            match t {
+                DisplayFormatType::Graphviz => write_graphviz_node(f, self),
+                _ => write!(f, "{}", self.label),
+            }
+        }
+    }
+
+    fn mock_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]))
+    }
+
+    fn leaf(label: &'static str) -> Arc<dyn ExecutionPlan> {
+        Arc::new(MockExec {
+            label,
+            schema: mock_schema(),
+            children: vec![],
+        })
+    }
+
+    #[test]
+    fn json_value_includes_name_and_no_children_for_leaf() {
+        let plan = leaf("LeafExec");
+        let value = json_value(plan.as_ref());
+        assert_eq!(value["name"], "LeafExec");
+        assert_eq!(value["children"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn json_value_recurses_into_children() {
+        let child = leaf("ChildExec");
+        let parent: Arc<dyn ExecutionPlan> = Arc::new(MockExec {
+            label: "ParentExec",
+            schema: mock_schema(),
+            children: vec![child],
+        });
+        let value = json_value(parent.as_ref());
+        assert_eq!(value["name"], "ParentExec");
+        let children = value["children"].as_array().unwrap();
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0]["name"], "ChildExec");
+    }
+
+    #[test]
+    fn json_value_escapes_quotes_in_label() {
+        // The label ends up inside `"name"` via serde_json, so an embedded
+        // quote must not produce invalid JSON.
+        let plan = leaf("Exec(\"quoted\")");
+        let rendered = json_value(plan.as_ref()).to_string();
+        let reparsed: serde_json::Value = serde_json::from_str(&rendered).unwrap();
+        assert_eq!(reparsed["name"], "Exec(\"quoted\")");
+    }
+
+    #[test]
+    fn escape_dot_escapes_backslash_and_quote() {
+        assert_eq!(escape_dot(r#"a\b"#), r#"a\\b"#);
+        assert_eq!(escape_dot(r#"say "hi""#), r#"say \"hi\""#);
+    }
+
+    #[test]
+    fn graphviz_escapes_a_label_containing_a_quote() {
+        // fmt_as's output is embedded verbatim in a quoted DOT label; an
+        // unescaped `"` would produce an unparsable digraph.
+        let plan = leaf("Exec(\"quoted\")");
+        let rendered = Graphviz(plan.as_ref()).to_string();
+        assert!(rendered.contains(r#"label="Exec(\"quoted\")"#));
+    }
+
+    #[test]
+    fn graphviz_emits_one_node_per_operator_and_an_edge_between_parent_and_child() {
+        let child = leaf("ChildExec");
+        let parent: Arc<dyn ExecutionPlan> = Arc::new(MockExec {
+            label: "ParentExec",
+            schema: mock_schema(),
+            children: vec![child],
+        });
+        let rendered = Graphviz(parent.as_ref()).to_string();
+        assert!(rendered.starts_with("digraph {\n"));
+        assert!(rendered.trim_end().ends_with('}'));
+        assert!(rendered.contains("n0 [label=\"ParentExec\"]"));
+        assert!(rendered.contains("n1 [label=\"ChildExec\"]"));
+        assert!(rendered.contains("n0 -> n1;"));
+    }
+}
@@ -0,0 +1,90 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the distribution an [`ExecutionPlan`](super::ExecutionPlan)
+//! requires of its input partitions
+
+use std::fmt;
+
+use crate::logical_plan::Expr;
+
+/// Distribution requirement that a physical operator imposes on the
+/// partitioning of its input, returned from
+/// `ExecutionPlan::required_child_distribution()`. `EXPLAIN` shows it next
+/// to each node's `output_partitioning()`; nothing in this module inserts a
+/// merge/repartition boundary where a child does not satisfy it.
+#[derive(Debug, Clone)]
+pub enum Distribution {
+    /// No requirement is placed on how the input is partitioned
+    UnspecifiedDistribution,
+    /// All of the input rows must be gathered into a single partition
+    SinglePartition,
+    /// Rows must be hash-partitioned on the given expressions, e.g. the join
+    /// keys of a hash join's probe side
+    HashPartitioned(Vec<Expr>),
+}
+
+impl fmt::Display for Distribution {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Distribution::UnspecifiedDistribution => write!(f, "UnspecifiedDistribution"),
+            Distribution::SinglePartition => write!(f, "SinglePartition"),
+            Distribution::HashPartitioned(exprs) => {
+                write!(f, "HashPartitioned([")?;
+                for (i, e) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{:?}", e)?;
+                }
+                write!(f, "])")
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unspecified_and_single_partition_display() {
+        assert_eq!(
+            Distribution::UnspecifiedDistribution.to_string(),
+            "UnspecifiedDistribution"
+        );
+        assert_eq!(Distribution::SinglePartition.to_string(), "SinglePartition");
+    }
+
+    #[test]
+    fn hash_partitioned_displays_comma_joined_exprs() {
+        let dist = Distribution::HashPartitioned(vec![
+            Expr::Column("a".to_string()),
+            Expr::Column("b".to_string()),
+        ]);
+        assert_eq!(
+            dist.to_string(),
+            "HashPartitioned([Column(\"a\"), Column(\"b\")])"
+        );
+    }
+
+    #[test]
+    fn hash_partitioned_with_no_exprs_displays_empty_list() {
+        let dist = Distribution::HashPartitioned(vec![]);
+        assert_eq!(dist.to_string(), "HashPartitioned([])");
+    }
+}
@@ -22,10 +22,11 @@ use std::sync::Arc;
 
 use crate::{
     error::{DataFusionError, Result},
-    logical_plan::StringifiedPlan,
+    logical_plan::{PlanType, StringifiedPlan},
     physical_plan::Partitioning,
     physical_plan::{common::SizedRecordBatchStream, DisplayFormatType, ExecutionPlan},
 };
+use crate::physical_plan::display::{self, walk_plan, IndentedNode};
 use crate::physical_plan::LambdaExecPlan;
 use arrow::{array::StringBuilder, datatypes::SchemaRef, record_batch::RecordBatch};
 
@@ -34,6 +35,24 @@ use async_trait::async_trait;
 
 use serde::{Deserialize, Serialize};
 
+/// Which single extra representation of the physical plan, if any,
+/// `ExplainExec` should produce alongside its normal per-stage
+/// `stringified_plans`, e.g. selected by `EXPLAIN (FORMAT ...) ...` SQL
+/// syntax. Only one of these is ever produced by a given `EXPLAIN`; unlike
+/// `stringified_plans`, they are not gated by `verbose`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ExplainFormat {
+    /// Annotate each node with its `output_partitioning()` and
+    /// `required_child_distribution()`, see [`ExplainExec::annotated_plan`]
+    Partitioning,
+    /// Serialize the plan tree as a single JSON string, see
+    /// [`ExplainExec::to_json`]
+    Json,
+    /// Render the plan tree as a Graphviz `digraph`, see
+    /// [`ExplainExec::to_graphviz`]
+    Graphviz,
+}
+
 /// Explain execution plan operator. This operator contains the string
 /// values of the various plans it has when it is created, and passes
 /// them to its output.
@@ -43,14 +62,33 @@ pub struct ExplainExec {
     schema: SchemaRef,
     /// The strings to be printed
     stringified_plans: Vec<StringifiedPlan>,
+    /// Should extra details be printed (e.g. the intermediate stages
+    /// produced by each optimizer rule), set from the SQL `EXPLAIN VERBOSE`
+    /// keyword
+    verbose: bool,
+    /// The physical plan being explained, kept around so `format` can be
+    /// rendered from it
+    plan: Option<Arc<dyn ExecutionPlan>>,
+    /// The single extra representation of `plan` to append to the output,
+    /// if any
+    format: Option<ExplainFormat>,
 }
 
 impl ExplainExec {
     /// Create a new ExplainExec
-    pub fn new(schema: SchemaRef, stringified_plans: Vec<StringifiedPlan>) -> Self {
+    pub fn new(
+        schema: SchemaRef,
+        stringified_plans: Vec<StringifiedPlan>,
+        verbose: bool,
+        plan: Option<Arc<dyn ExecutionPlan>>,
+        format: Option<ExplainFormat>,
+    ) -> Self {
         ExplainExec {
             schema,
             stringified_plans,
+            verbose,
+            plan,
+            format,
         }
     }
 
@@ -58,6 +96,50 @@ impl ExplainExec {
     pub fn stringified_plans(&self) -> &[StringifiedPlan] {
         &self.stringified_plans
     }
+
+    /// Access to the verbose flag
+    pub fn verbose(&self) -> bool {
+        self.verbose
+    }
+
+    /// The extra representation of the plan, if any, selected for this
+    /// `EXPLAIN`
+    pub fn format(&self) -> Option<ExplainFormat> {
+        self.format
+    }
+
+    /// Render the plan being explained with each node's
+    /// `output_partitioning()` and `required_child_distribution()`
+    /// annotated, e.g. to see where a repartition/merge boundary would be
+    /// inserted. Returns `None` if this `ExplainExec` was not given a plan.
+    pub fn annotated_plan(&self) -> Option<String> {
+        self.plan.as_ref().map(|p| {
+            let mut lines = Vec::new();
+            walk_plan(p.as_ref(), 0, &mut lines, &|node| {
+                IndentedNode(node).to_string()
+            });
+            lines.join("\n")
+        })
+    }
+
+    /// Render the plan being explained as a single, compact JSON string so
+    /// a coordinator can inspect a plan before dispatching it to a remote
+    /// lambda worker. Returns `None` if this `ExplainExec` was not given a
+    /// plan.
+    pub fn to_json(&self) -> Option<String> {
+        self.plan
+            .as_ref()
+            .map(|p| display::Json(p.as_ref()).to_string())
+    }
+
+    /// Render the plan being explained as a Graphviz `digraph`, so it can be
+    /// piped into `dot` to visualize it. Returns `None` if this
+    /// `ExplainExec` was not given a plan.
+    pub fn to_graphviz(&self) -> Option<String> {
+        self.plan
+            .as_ref()
+            .map(|p| display::Graphviz(p.as_ref()).to_string())
+    }
 }
 
 #[async_trait]
@@ -112,10 +194,38 @@ impl ExecutionPlan for ExplainExec {
         let mut plan_builder = StringBuilder::new(self.stringified_plans.len());
 
         for p in &self.stringified_plans {
+            if !p.should_display(self.verbose) {
+                continue;
+            }
             type_builder.append_value(&String::from(&p.plan_type))?;
             plan_builder.append_value(&*p.plan)?;
         }
 
+        match self.format {
+            Some(ExplainFormat::Partitioning) => {
+                if let Some(annotated) = self.annotated_plan() {
+                    let plan_type = PlanType::PhysicalPlanWithPartitioning;
+                    type_builder.append_value(&String::from(&plan_type))?;
+                    plan_builder.append_value(&annotated)?;
+                }
+            }
+            Some(ExplainFormat::Json) => {
+                if let Some(json) = self.to_json() {
+                    let plan_type = PlanType::PhysicalPlanJson;
+                    type_builder.append_value(&String::from(&plan_type))?;
+                    plan_builder.append_value(&json)?;
+                }
+            }
+            Some(ExplainFormat::Graphviz) => {
+                if let Some(graphviz) = self.to_graphviz() {
+                    let plan_type = PlanType::PhysicalPlanGraphviz;
+                    type_builder.append_value(&String::from(&plan_type))?;
+                    plan_builder.append_value(&graphviz)?;
+                }
+            }
+            None => {}
+        }
+
         let record_batch = RecordBatch::try_new(
             self.schema.clone(),
             vec![
@@ -139,6 +249,8 @@ impl ExecutionPlan for ExplainExec {
             DisplayFormatType::Default => {
                 write!(f, "ExplainExec")
             }
+            DisplayFormatType::Json => display::write_json_node(f, self),
+            DisplayFormatType::Graphviz => display::write_graphviz_node(f, self),
         }
     }
 }
@@ -149,3 +261,112 @@ impl LambdaExecPlan for ExplainExec {
         unimplemented!();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_plan::Partitioning;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use futures::executor::block_on;
+    use futures::StreamExt;
+
+    /// A leaf plan with no children, just enough to be explained.
+    #[derive(Debug, Clone, Serialize, Deserialize)]
+    struct MockExec {
+        schema: SchemaRef,
+    }
+
+    #[async_trait]
+    #[typetag::serde(name = "mock_exec_for_explain_test")]
+    impl ExecutionPlan for MockExec {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn as_mut_any(&mut self) -> &mut dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+            vec![]
+        }
+
+        fn output_partitioning(&self) -> Partitioning {
+            Partitioning::UnknownPartitioning(1)
+        }
+
+        fn with_new_children(
+            &self,
+            _children: Vec<Arc<dyn ExecutionPlan>>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            Ok(Arc::new(self.clone()))
+        }
+
+        async fn execute(&self, _partition: usize) -> Result<SendableRecordBatchStream> {
+            unimplemented!()
+        }
+
+        fn fmt_as(
+            &self,
+            _t: DisplayFormatType,
+            f: &mut std::fmt::Formatter,
+        ) -> std::fmt::Result {
+            write!(f, "MockExec")
+        }
+    }
+
+    fn explain_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("plan_type", DataType::Utf8, false),
+            Field::new("plan", DataType::Utf8, false),
+        ]))
+    }
+
+    fn mock_plan() -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        Arc::new(MockExec { schema })
+    }
+
+    /// With no stringified_plans and no format selected, execute() should
+    /// produce no rows at all.
+    #[test]
+    fn no_format_produces_no_extra_rows() {
+        let exec = ExplainExec::new(explain_schema(), vec![], false, Some(mock_plan()), None);
+        let mut stream = block_on(exec.execute(0)).unwrap();
+        let batch = block_on(stream.next()).unwrap().unwrap();
+        assert_eq!(batch.num_rows(), 0);
+    }
+
+    /// Each `Some(format)` should append exactly one extra row, holding that
+    /// format's rendering of `plan`.
+    #[test]
+    fn each_format_produces_exactly_one_row() {
+        for (format, plan_type) in [
+            (ExplainFormat::Partitioning, "physical_plan_with_partitioning"),
+            (ExplainFormat::Json, "physical_plan_json"),
+            (ExplainFormat::Graphviz, "physical_plan_graphviz"),
+        ] {
+            let exec = ExplainExec::new(
+                explain_schema(),
+                vec![],
+                false,
+                Some(mock_plan()),
+                Some(format),
+            );
+            let mut stream = block_on(exec.execute(0)).unwrap();
+            let batch = block_on(stream.next()).unwrap().unwrap();
+            assert_eq!(batch.num_rows(), 1, "format {:?} should add one row", format);
+
+            let type_col = batch
+                .column(0)
+                .as_any()
+                .downcast_ref::<arrow::array::StringArray>()
+                .unwrap();
+            assert_eq!(type_col.value(0), plan_type);
+        }
+    }
+}
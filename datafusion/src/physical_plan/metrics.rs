@@ -0,0 +1,200 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Runtime metrics recorded by an [`ExecutionPlan`](super::ExecutionPlan)
+//! while it streams, surfaced by `EXPLAIN ANALYZE`.
+
+use std::fmt;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+
+/// An atomically updated scalar counter, e.g. the number of rows an
+/// operator has produced so far.
+#[derive(Debug, Default)]
+pub struct Count {
+    value: AtomicUsize,
+}
+
+impl Count {
+    /// Create a new counter initialized to zero
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `n` to the current value
+    pub fn add(&self, n: usize) {
+        self.value.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// The current value of the counter
+    pub fn value(&self) -> usize {
+        self.value.load(Ordering::Relaxed)
+    }
+}
+
+impl fmt::Display for Count {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.value())
+    }
+}
+
+/// An atomically updated timer that accumulates wall-clock time spent in an
+/// operator across however many times it is started and stopped.
+#[derive(Debug, Default)]
+pub struct Time {
+    nanos: AtomicUsize,
+}
+
+impl Time {
+    /// Create a new, zeroed timer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add `duration` to the time recorded so far
+    pub fn add(&self, duration: Duration) {
+        self.nanos
+            .fetch_add(duration.as_nanos() as usize, Ordering::Relaxed);
+    }
+
+    /// Start a scoped timer that adds the elapsed time to this `Time` when
+    /// it is dropped, so operators can simply hold the guard across the
+    /// region of code being measured.
+    pub fn timer(&self) -> ScopedTimerGuard<'_> {
+        ScopedTimerGuard {
+            time: self,
+            start: Instant::now(),
+        }
+    }
+
+    /// The total duration recorded so far
+    pub fn value(&self) -> Duration {
+        Duration::from_nanos(self.nanos.load(Ordering::Relaxed) as u64)
+    }
+}
+
+impl fmt::Display for Time {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?}", self.value())
+    }
+}
+
+/// RAII guard returned by [`Time::timer`] that records the elapsed time into
+/// the originating [`Time`] when dropped.
+pub struct ScopedTimerGuard<'a> {
+    time: &'a Time,
+    start: Instant,
+}
+
+impl<'a> Drop for ScopedTimerGuard<'a> {
+    fn drop(&mut self) {
+        self.time.add(self.start.elapsed());
+    }
+}
+
+/// A snapshot of the metrics a single `ExecutionPlan` node has recorded,
+/// returned from `ExecutionPlan::metrics()`.
+#[derive(Debug, Default)]
+pub struct MetricsSet {
+    /// Number of rows produced by this operator so far
+    pub output_rows: Count,
+    /// Wall-clock time this operator has spent computing output
+    pub elapsed_compute: Time,
+    /// Number of partitions this operator has executed
+    pub partition_count: Count,
+}
+
+impl MetricsSet {
+    /// Create an empty metrics set
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Take a point-in-time copy of the current values. `MetricsSet` itself
+    /// can't derive `Clone` (its counters are atomics), but
+    /// `ExecutionPlan::metrics()` needs to hand back an owned value, so
+    /// callers that record metrics behind an `Arc<MetricsSet>` use this to
+    /// produce the snapshot they return.
+    pub fn snapshot(&self) -> Self {
+        let snapshot = Self::new();
+        snapshot.output_rows.add(self.output_rows.value());
+        snapshot
+            .elapsed_compute
+            .add(self.elapsed_compute.value());
+        snapshot.partition_count.add(self.partition_count.value());
+        snapshot
+    }
+}
+
+impl fmt::Display for MetricsSet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "output_rows={}, elapsed_compute={}, partitions={}",
+            self.output_rows, self.elapsed_compute, self.partition_count
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_accumulates() {
+        let count = Count::new();
+        assert_eq!(count.value(), 0);
+        count.add(3);
+        count.add(4);
+        assert_eq!(count.value(), 7);
+    }
+
+    #[test]
+    fn time_accumulates_across_timers() {
+        let time = Time::new();
+        time.add(Duration::from_millis(5));
+        {
+            let _timer = time.timer();
+        }
+        assert!(time.value() >= Duration::from_millis(5));
+    }
+
+    #[test]
+    fn metrics_set_snapshot_is_independent() {
+        let metrics = MetricsSet::new();
+        metrics.output_rows.add(42);
+        metrics.partition_count.add(2);
+
+        let snapshot = metrics.snapshot();
+        assert_eq!(snapshot.output_rows.value(), 42);
+        assert_eq!(snapshot.partition_count.value(), 2);
+
+        // further updates to the live set must not leak into the snapshot
+        metrics.output_rows.add(1);
+        assert_eq!(snapshot.output_rows.value(), 42);
+    }
+
+    #[test]
+    fn metrics_set_display_format() {
+        let metrics = MetricsSet::new();
+        metrics.output_rows.add(10);
+        metrics.partition_count.add(1);
+        let rendered = metrics.to_string();
+        assert!(rendered.contains("output_rows=10"));
+        assert!(rendered.contains("partitions=1"));
+    }
+}
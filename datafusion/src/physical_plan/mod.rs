@@ -0,0 +1,134 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Traits and types for physical query execution
+
+pub mod analyze;
+pub mod common;
+pub mod display;
+pub mod distribution;
+pub mod explain;
+pub mod metrics;
+
+use std::any::Any;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use async_trait::async_trait;
+use futures::Stream;
+
+use crate::error::Result;
+pub use distribution::Distribution;
+pub use metrics::MetricsSet;
+
+/// How an operator's output rows are divided across partitions
+#[derive(Debug, Clone)]
+pub enum Partitioning {
+    /// An unknown or unspecified partitioning scheme with a fixed partition
+    /// count
+    UnknownPartitioning(usize),
+}
+
+impl Partitioning {
+    /// The number of partitions this scheme divides rows into
+    pub fn partition_count(&self) -> usize {
+        match self {
+            Partitioning::UnknownPartitioning(n) => *n,
+        }
+    }
+}
+
+/// Controls how `ExecutionPlan::fmt_as` renders a node
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DisplayFormatType {
+    /// Default, single-line, human readable representation
+    Default,
+    /// Compact, machine-readable JSON representation, see
+    /// [`display::write_json_node`]
+    Json,
+    /// Graphviz `digraph` representation, see
+    /// [`display::write_graphviz_node`]
+    Graphviz,
+}
+
+/// A stream of `RecordBatch`es produced by executing a partition of an
+/// `ExecutionPlan`
+pub type SendableRecordBatchStream = Pin<Box<dyn Stream<Item = Result<RecordBatch>> + Send>>;
+
+/// A physical operator that can be executed against one of its partitions
+/// to produce a stream of `RecordBatch`es
+#[async_trait]
+#[typetag::serde(tag = "type")]
+pub trait ExecutionPlan: std::fmt::Debug + Send + Sync {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any;
+
+    /// Return a mutable reference to Any that can be used for downcasting
+    fn as_mut_any(&mut self) -> &mut dyn Any;
+
+    /// The schema of the `RecordBatch`es this plan produces
+    fn schema(&self) -> SchemaRef;
+
+    /// The immediate child plans that feed this operator
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>>;
+
+    /// How this operator's output is partitioned
+    fn output_partitioning(&self) -> Partitioning;
+
+    /// The distribution this operator requires of its input partitions.
+    /// Defaults to [`Distribution::UnspecifiedDistribution`]; operators like
+    /// a hash join or a global sort override this to require their input be
+    /// gathered or hash-partitioned accordingly.
+    fn required_child_distribution(&self) -> Distribution {
+        Distribution::UnspecifiedDistribution
+    }
+
+    /// Return a new plan with `children` substituted for the current
+    /// children
+    fn with_new_children(
+        &self,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Begin execution of `partition`, returning a stream of the
+    /// `RecordBatch`es it produces
+    async fn execute(&self, partition: usize) -> Result<SendableRecordBatchStream>;
+
+    /// The runtime metrics this operator has recorded while executing, if
+    /// it records any. Defaults to `None`.
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    /// Write a representation of this node to `f` in the style requested by
+    /// `t`. The `Default` style covers this node only, not its children; the
+    /// `Json` and `Graphviz` styles cover the whole subtree rooted at it.
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result;
+}
+
+/// Implemented by operators that can be fed pre-computed partitions in place
+/// of executing their normal input, used to run a plan fragment dispatched
+/// to a remote lambda worker.
+#[async_trait]
+pub trait LambdaExecPlan {
+    /// Replace this operator's input with already-computed batches, one
+    /// `Vec<RecordBatch>` per partition
+    fn feed_batches(&mut self, partitions: Vec<Vec<RecordBatch>>);
+}